@@ -1,14 +1,36 @@
 //! Converts Wareki (JIS X 0301) based date into ISO 8601 based one
 
 use chrono::prelude::*;
+use chrono::Duration;
 use kana::*;
 use regex::Regex;
 
-const START_YEAR_OF_MEIJI: i32 = 1868;
-const START_YEAR_OF_TAISHO: i32 = 1912;
-const START_YEAR_OF_SHOWA: i32 = 1926;
-const START_YEAR_OF_HEISEI: i32 = 1989;
-const START_YEAR_OF_REIWA: i32 = 2019;
+/// Offset between the Kōki (皇紀) and Gregorian calendars: Kōki counts years
+/// from the legendary founding of Japan in 660 BC.
+const KOKI_EPOCH_OFFSET: i32 = 660;
+
+/// Error returned when a Wareki string cannot be parsed or converted.
+///
+/// Replaces the `assert!`/`.unwrap()` panics [`find_type`] and [`convert`]
+/// used to raise on malformed input: callers now get a `Result` describing
+/// what went wrong instead of the process aborting.
+#[derive(Debug)]
+pub enum WarekiError {
+    /// The input did not match any of the notations [`find_type`] knows
+    /// about (e.g. wrong number of `.`-separated fields).
+    UnrecognizedFormat,
+    /// A year/month/day field was present but not a valid number.
+    InvalidNumber,
+    /// The year/month/day fields were numbers, but do not form a valid
+    /// calendar date.
+    InvalidDate,
+    /// The Gengo meta character/name could not be resolved.
+    UnknownEra,
+    /// The date falls outside the range the matched Gengo was actually in
+    /// effect, e.g. `昭和64年1月8日` (Showa 64 only ran to 01-07). Only
+    /// returned by [`convert_strict`].
+    OutOfEraRange,
+}
 
 /// Struct for date
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -40,37 +62,156 @@ impl Date {
     }
 }
 
-/// List of Gengo
+/// A named Japanese era (元号; Gengo, also known as Nengō).
+///
+/// Unlike the fixed five-variant enum this used to be, a `Gengo` is now a
+/// row out of [`gengo_table`], which makes it possible to look up eras
+/// beyond the five modern ones without touching any matching logic.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum Gengo {
-    /// Meiji
-    Meiji,
-    /// Taisho
-    Taisho,
-    /// Showa
-    Showa,
-    /// Heisei
-    Heisei,
-    /// Reiwa
-    Reiwa,
+pub struct Gengo {
+    kanji: &'static str,
+    romaji: &'static str,
+    start: NaiveDate,
+}
+
+/// Built-in Gengo (Nengō) table, newest first.
+///
+/// Ships with the five modern eras plus every single-court era back through
+/// 応永/Oei (1394), covering the Edo, Sengoku and Muromachi periods; since
+/// lookup ([`Gengo::from_date`], [`gengo_resolve`]) walks this table rather
+/// than matching on hardcoded variants, further eras can be added here
+/// without touching any matching logic.
+///
+/// ## Scope
+/// The table stops at Oei rather than continuing into the classical period
+/// because of the Nanboku-chō (Northern and Southern Courts) period
+/// (1331-1392) immediately before it: for those six decades Japan had two
+/// rival courts in simultaneous use, each naming its own era, so a single
+/// `start` date per era name can't represent it without [`Gengo`] itself
+/// changing shape (e.g. tracking which court an era belongs to). Extending
+/// further back into Kamakura/Heian/Nara eras is possible once that's
+/// modeled, but is a structural change, not just more table rows, so it's
+/// left for a follow-up request rather than bolted on here.
+fn gengo_table() -> Vec<Gengo> {
+    fn gengo(kanji: &'static str, romaji: &'static str, y: i32, m: u32, d: u32) -> Gengo {
+        Gengo {
+            kanji,
+            romaji,
+            start: NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+        }
+    }
+
+    vec![
+        gengo("令和", "Reiwa", 2019, 5, 1),
+        gengo("平成", "Heisei", 1989, 1, 8),
+        gengo("昭和", "Showa", 1926, 12, 25),
+        gengo("大正", "Taisho", 1912, 7, 30),
+        gengo("明治", "Meiji", 1868, 9, 8),
+        gengo("慶応", "Keio", 1865, 4, 7),
+        gengo("元治", "Genji", 1864, 3, 27),
+        gengo("文久", "Bunkyu", 1861, 3, 29),
+        gengo("万延", "Manen", 1860, 4, 8),
+        gengo("安政", "Ansei", 1855, 1, 15),
+        gengo("嘉永", "Kaei", 1848, 4, 1),
+        gengo("弘化", "Koka", 1845, 1, 9),
+        gengo("天保", "Tenpo", 1831, 1, 23),
+        gengo("文政", "Bunsei", 1818, 5, 26),
+        gengo("文化", "Bunka", 1804, 3, 22),
+        gengo("享和", "Kyowa", 1801, 3, 19),
+        gengo("寛政", "Kansei", 1789, 2, 19),
+        gengo("天明", "Tenmei", 1781, 4, 25),
+        gengo("安永", "Anei", 1772, 12, 10),
+        gengo("明和", "Meiwa", 1764, 6, 30),
+        gengo("宝暦", "Horeki", 1751, 12, 14),
+        gengo("寛延", "Kanen", 1748, 8, 5),
+        gengo("延享", "Enkyo", 1744, 4, 3),
+        gengo("寛保", "Kanpo", 1741, 4, 12),
+        gengo("元文", "Genbun", 1736, 6, 7),
+        gengo("享保", "Kyoho", 1716, 8, 9),
+        gengo("正徳", "Shotoku", 1711, 6, 11),
+        gengo("宝永", "Hoei", 1704, 3, 13),
+        gengo("元禄", "Genroku", 1688, 10, 23),
+        gengo("貞享", "Jokyo", 1684, 4, 5),
+        gengo("天和", "Tenna", 1681, 11, 9),
+        gengo("延宝", "Enpo", 1673, 10, 30),
+        gengo("寛文", "Kanbun", 1661, 5, 23),
+        gengo("万治", "Manji", 1658, 8, 21),
+        gengo("明暦", "Meireki", 1655, 5, 18),
+        gengo("承応", "Joo", 1652, 10, 20),
+        gengo("慶安", "Keian", 1648, 4, 7),
+        gengo("正保", "Shoho", 1645, 1, 13),
+        gengo("寛永", "Kanei", 1624, 4, 17),
+        gengo("元和", "Genna", 1615, 9, 5),
+        gengo("慶長", "Keicho", 1596, 12, 16),
+        gengo("文禄", "Bunroku", 1593, 1, 10),
+        gengo("天正", "Tensho", 1573, 8, 25),
+        gengo("元亀", "Genki", 1570, 5, 27),
+        gengo("永禄", "Eiroku", 1558, 3, 18),
+        gengo("弘治", "Koji", 1555, 11, 7),
+        gengo("天文", "Tenbun", 1532, 8, 29),
+        gengo("享禄", "Kyoroku", 1528, 9, 3),
+        gengo("大永", "Daiei", 1521, 9, 23),
+        gengo("永正", "Eisho", 1504, 3, 16),
+        gengo("文亀", "Bunki", 1501, 3, 18),
+        gengo("明応", "Meio", 1492, 8, 12),
+        gengo("延徳", "Entoku", 1489, 9, 16),
+        gengo("長享", "Chokyo", 1487, 8, 9),
+        gengo("文明", "Bunmei", 1469, 6, 8),
+        gengo("応仁", "Onin", 1467, 3, 16),
+        gengo("文正", "Bunsho", 1466, 3, 14),
+        gengo("寛正", "Kansho", 1461, 2, 1),
+        gengo("長禄", "Choroku", 1457, 10, 16),
+        gengo("康正", "Kosho", 1455, 9, 6),
+        gengo("享徳", "Kyotoku", 1452, 8, 10),
+        gengo("宝徳", "Hotoku", 1449, 8, 16),
+        gengo("文安", "Bunan", 1444, 9, 17),
+        gengo("嘉吉", "Kakitsu", 1441, 3, 10),
+        gengo("永享", "Eikyo", 1429, 10, 3),
+        gengo("正長", "Shocho", 1428, 6, 10),
+        gengo("応永", "Oei", 1394, 8, 2),
+    ]
 }
 
 impl Gengo {
+    /// Looks up a modern era by its romaji name, so the accessors below
+    /// stay correct regardless of `gengo_table`'s order or length.
+    fn by_romaji(romaji: &str) -> Self {
+        gengo_table()
+            .into_iter()
+            .find(|gengo| gengo.romaji == romaji)
+            .unwrap_or_else(|| panic!("gengo_table is missing the {} era", romaji))
+    }
+
+    /// Returns the modern Reiwa era.
+    pub fn reiwa() -> Self {
+        Self::by_romaji("Reiwa")
+    }
+    /// Returns the modern Heisei era.
+    pub fn heisei() -> Self {
+        Self::by_romaji("Heisei")
+    }
+    /// Returns the modern Showa era.
+    pub fn showa() -> Self {
+        Self::by_romaji("Showa")
+    }
+    /// Returns the modern Taisho era.
+    pub fn taisho() -> Self {
+        Self::by_romaji("Taisho")
+    }
+    /// Returns the modern Meiji era.
+    pub fn meiji() -> Self {
+        Self::by_romaji("Meiji")
+    }
+
     /// Returns the first year of the Gengo
     ///
     /// ```rust
     /// use wareki_conv::conv::Gengo;
     ///
-    /// assert_eq!(Gengo::Meiji.first_year(), 1868)
+    /// assert_eq!(Gengo::meiji().first_year(), 1868)
     /// ```
-    pub const fn first_year(&self) -> i32 {
-        match *self {
-            Gengo::Meiji => START_YEAR_OF_MEIJI,
-            Gengo::Taisho => START_YEAR_OF_TAISHO,
-            Gengo::Showa => START_YEAR_OF_SHOWA,
-            Gengo::Heisei => START_YEAR_OF_HEISEI,
-            Gengo::Reiwa => START_YEAR_OF_REIWA,
-        }
+    pub fn first_year(&self) -> i32 {
+        self.start.year()
     }
 
     /// Get the name of the Gengo
@@ -78,17 +219,145 @@ impl Gengo {
     /// ```rust
     /// use wareki_conv::conv::Gengo;
     ///
-    /// assert_eq!(Gengo::Meiji.name(), "Meiji")
+    /// assert_eq!(Gengo::meiji().name(), "Meiji")
     /// ```
-    pub const fn name(&self) -> &'static str {
-        match *self {
-            Gengo::Meiji => "Meiji",
-            Gengo::Taisho => "Taisho",
-            Gengo::Showa => "Showa",
-            Gengo::Heisei => "Heisei",
-            Gengo::Reiwa => "Reiwa",
-        }
+    pub fn name(&self) -> &'static str {
+        self.romaji
+    }
+
+    /// Get the full kanji name of the Gengo
+    ///
+    /// ```rust
+    /// use wareki_conv::conv::Gengo;
+    ///
+    /// assert_eq!(Gengo::meiji().kanji_name(), "明治")
+    /// ```
+    pub fn kanji_name(&self) -> &'static str {
+        self.kanji
     }
+
+    /// Returns the date the Gengo actually begins on.
+    ///
+    /// Unlike [`Gengo::first_year`], which only carries the year, this is
+    /// the exact day the era starts, since eras begin mid-year rather than
+    /// on January 1st.
+    ///
+    /// ```rust
+    /// use chrono::prelude::*;
+    /// use wareki_conv::conv::Gengo;
+    ///
+    /// assert_eq!(
+    ///     Gengo::heisei().start_date(),
+    ///     NaiveDate::from_ymd_opt(1989, 1, 8).unwrap()
+    /// )
+    /// ```
+    pub fn start_date(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// Resolves the Gengo that a Gregorian date falls into, i.e. the latest
+    /// era in [`gengo_table`] whose [`Gengo::start_date`] is on or before
+    /// `date`.
+    ///
+    /// Returns `None` for dates before the oldest era in the table.
+    ///
+    /// ```rust
+    /// use chrono::prelude::*;
+    /// use wareki_conv::conv::Gengo;
+    ///
+    /// assert_eq!(
+    ///     Gengo::from_date(Utc.with_ymd_and_hms(2019, 5, 1, 0, 0, 0).unwrap()),
+    ///     Some(Gengo::reiwa())
+    /// );
+    /// ```
+    pub fn from_date(date: DateTime<Utc>) -> Option<Gengo> {
+        let date = date.date_naive();
+        gengo_table().into_iter().find(|gengo| gengo.start <= date)
+    }
+
+    /// Returns the last date the Gengo is valid on, i.e. the day before the
+    /// next (more recent) era in [`gengo_table`] begins. Returns `None` for
+    /// the newest era in the table, since it has no known end yet.
+    ///
+    /// ```rust
+    /// use chrono::prelude::*;
+    /// use wareki_conv::conv::Gengo;
+    ///
+    /// assert_eq!(
+    ///     Gengo::showa().end_date(),
+    ///     Some(NaiveDate::from_ymd_opt(1989, 1, 7).unwrap())
+    /// );
+    /// assert_eq!(Gengo::reiwa().end_date(), None);
+    /// ```
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        let table = gengo_table();
+        table
+            .windows(2)
+            .find(|pair| pair[1].start == self.start)
+            .map(|pair| pair[0].start - Duration::days(1))
+    }
+}
+
+/// A Gregorian date resolved into its Wareki representation.
+///
+/// Returned by [`to_wareki`]; carries the matched [`Gengo`] plus the era
+/// year, month and day so callers can render it in whichever [`DateType`]
+/// notation they need.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Wareki {
+    /// The era the date falls into
+    pub gengo: Gengo,
+    /// Year within the era (1 is the era's first year)
+    pub year: i32,
+    /// Month
+    pub month: u32,
+    /// Day
+    pub day: u32,
+}
+
+/// Converts an ISO 8601 (Gregorian) date into Wareki.
+///
+/// This is the inverse of [`convert`]. Returns `None` if `date` is before
+/// the oldest era in [`gengo_table`].
+///
+/// ## Example
+/// ```rust
+/// use chrono::prelude::*;
+/// use wareki_conv::conv::{to_wareki, Gengo, Wareki};
+///
+/// assert_eq!(
+///     to_wareki(Utc.with_ymd_and_hms(2019, 5, 1, 0, 0, 0).unwrap()),
+///     Some(Wareki { gengo: Gengo::reiwa(), year: 1, month: 5, day: 1 })
+/// );
+/// ```
+pub fn to_wareki(date: DateTime<Utc>) -> Option<Wareki> {
+    let gengo = Gengo::from_date(date)?;
+    let year = date.year() - gengo.first_year() + 1;
+
+    Some(Wareki {
+        gengo,
+        year,
+        month: date.month(),
+        day: date.day(),
+    })
+}
+
+/// Converts a Gregorian date into its Kōki (皇紀; Imperial year) year number.
+///
+/// Kōki is a well-established alternate year numbering that counts from the
+/// legendary founding of Japan, so it is simply the Gregorian year offset by
+/// [`KOKI_EPOCH_OFFSET`], unlike [`to_wareki`], which requires looking up a
+/// Gengo.
+///
+/// ## Example
+/// ```rust
+/// use chrono::prelude::*;
+/// use wareki_conv::conv::to_koki;
+///
+/// assert_eq!(to_koki(Utc.with_ymd_and_hms(1940, 1, 1, 0, 0, 0).unwrap()), 2600);
+/// ```
+pub fn to_koki(date: DateTime<Utc>) -> i32 {
+    date.year() + KOKI_EPOCH_OFFSET
 }
 
 /// Date type
@@ -101,6 +370,7 @@ impl Gengo {
 /// |         `JisX0301Extended`          |   `R01.02.03`   |
 /// |     `JisX0301ExtendedWithKanji`     |  `令01.02.03`   |
 /// |        `SeparatedWithKanji`         | `令和1年2月3日` |
+/// |               `Koki`                | `皇紀2600年1月1日` |
 ///
 /// ## Remark
 /// JIS X 0301 requires each value (year, month and day) to be padded with 0
@@ -116,6 +386,97 @@ pub enum DateType {
     JisX0301Extended,
     JisX0301ExtendedWithKanji,
     SeparatedWithKanji,
+    /// Kōki (皇紀; Imperial year), counted from 660 BC
+    Koki,
+}
+
+/// Era-name rendering style used by [`format_wareki`]
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Hash)]
+pub enum EraStyle {
+    /// Full kanji era name, e.g. `令和`
+    KanjiFull,
+    /// Leading kanji character only, e.g. `令`
+    KanjiShort,
+    /// Full romaji era name, e.g. `Reiwa`
+    RomajiFull,
+    /// Leading romaji character only, e.g. `R`
+    RomajiInitial,
+    /// Full romaji era name, uppercased, e.g. `REIWA`
+    RomajiUpper,
+}
+
+impl EraStyle {
+    fn render(&self, gengo: &Gengo) -> String {
+        match self {
+            EraStyle::KanjiFull => gengo.kanji_name().to_string(),
+            EraStyle::KanjiShort => gengo
+                .kanji_name()
+                .chars()
+                .next()
+                .expect("kanji_name is never empty")
+                .to_string(),
+            EraStyle::RomajiFull => gengo.name().to_string(),
+            EraStyle::RomajiInitial => gengo
+                .name()
+                .chars()
+                .next()
+                .expect("name is never empty")
+                .to_string(),
+            EraStyle::RomajiUpper => gengo.name().to_uppercase(),
+        }
+    }
+}
+
+/// Renders a Wareki date (era, year, month, day) back into one of the
+/// notations [`find_type`] recognizes, using `style` for the era name.
+///
+/// This is the rendering counterpart to [`convert`]/[`to_wareki`]: given the
+/// pieces of a [`Wareki`], it produces the string a caller actually wants to
+/// display, including the romaji/kanji era-name variants commonly seen in
+/// Japanese date libraries. Set `first_year` to print `元年` instead of
+/// `1年` for `SeparatedWithKanji`, as is conventional for the first year of
+/// an era.
+///
+/// ## Example
+/// ```rust
+/// use wareki_conv::conv::{format_wareki, DateType, EraStyle, Gengo};
+///
+/// assert_eq!(
+///     format_wareki(&Gengo::reiwa(), 1, 2, 3, DateType::SeparatedWithKanji, EraStyle::KanjiFull, true),
+///     "令和元年2月3日"
+/// );
+/// assert_eq!(
+///     format_wareki(&Gengo::reiwa(), 10, 2, 3, DateType::JisX0301Extended, EraStyle::RomajiInitial, false),
+///     "R10.02.03"
+/// );
+/// ```
+pub fn format_wareki(
+    gengo: &Gengo,
+    year: i32,
+    month: u32,
+    day: u32,
+    date_type: DateType,
+    style: EraStyle,
+    first_year: bool,
+) -> String {
+    let era = style.render(gengo);
+
+    match date_type {
+        DateType::JisX0301Basic => format!("{:02}.{:02}.{:02}", year, month, day),
+        DateType::JisX0301Extended | DateType::JisX0301ExtendedWithKanji => {
+            format!("{}{:02}.{:02}.{:02}", era, year, month, day)
+        }
+        DateType::SeparatedWithKanji => {
+            let year_str = if first_year && year == 1 {
+                "元".to_string()
+            } else {
+                year.to_string()
+            };
+            format!("{}{}年{}月{}日", era, year_str, month, day)
+        }
+        // Koki has no Gengo, so `era`/`style` are ignored here.
+        DateType::Koki => format!("皇紀{}年{}月{}日", year, month, day),
+    }
 }
 
 /// Normalize input data
@@ -154,21 +515,39 @@ pub fn to_half_width(input: &str) -> String {
 ///     Some(DateType::JisX0301Extended)
 /// )
 /// ```
-pub fn find_type(wareki: &str) -> Result<Option<DateType>, regex::Error> {
+pub fn find_type(wareki: &str) -> Result<Option<DateType>, WarekiError> {
     let wareki_half = to_half_width(wareki);
     let elm: Vec<&str> = wareki_half.split('.').collect();
-    let re_begin_with_digit = Regex::new(r"^\d")?;
-    let re_begin_with_char = Regex::new(r"^(M|T|S|H|R)")?;
-    let re_begin_with_kanji = Regex::new(r"^(明|大|昭|平|令)")?;
-    let re_separated_with_kanji = Regex::new(r"^(明治|大正|昭和|平成|令和)\d+年\d+月\d+日")?;
+    // These patterns are fixed strings, so construction never fails.
+    let re_begin_with_digit = Regex::new(r"^\d").expect("static regex is valid");
+    let re_begin_with_char = Regex::new(r"^(M|T|S|H|R)").expect("static regex is valid");
+    let re_begin_with_kanji = Regex::new(r"^(明|大|昭|平|令)").expect("static regex is valid");
+    // Built from `gengo_table()` (rather than a fixed `明治|大正|...` list)
+    // so historical eras are recognized in `SeparatedWithKanji` input too.
+    let era_names = gengo_table()
+        .iter()
+        .map(|gengo| gengo.kanji)
+        .collect::<Vec<_>>()
+        .join("|");
+    let re_separated_with_kanji =
+        Regex::new(&format!(r"^({})\d+年\d+月\d+日", era_names)).expect("static regex is valid");
+    let re_koki = Regex::new(r"^皇紀\d+年\d+月\d+日").expect("static regex is valid");
 
     if elm.len() == 1 {
-        // A minimum syntax assertion
-        assert!(re_separated_with_kanji.is_match(elm.get(0).unwrap()));
-        return Ok(Some(DateType::SeparatedWithKanji));
+        let s = *elm.get(0).unwrap();
+        if re_koki.is_match(s) {
+            return Ok(Some(DateType::Koki));
+        }
+        if re_separated_with_kanji.is_match(s) {
+            return Ok(Some(DateType::SeparatedWithKanji));
+        }
+        return Err(WarekiError::UnrecognizedFormat);
+    }
+
+    if elm.len() != 3 {
+        return Err(WarekiError::UnrecognizedFormat);
     }
 
-    assert_eq!(elm.len(), 3);
     let date_type = match elm.get(0) {
         Some(x) if re_begin_with_digit.is_match(x) => Some(DateType::JisX0301Basic),
         Some(x) if re_begin_with_char.is_match(x) => Some(DateType::JisX0301Extended),
@@ -179,36 +558,69 @@ pub fn find_type(wareki: &str) -> Result<Option<DateType>, regex::Error> {
     Ok(date_type)
 }
 
-/// Maps meta character to corresponding Gengo
+/// Resolves the Gengo named or abbreviated in `wareki`.
+///
+/// Era names in [`gengo_table`] are matched as whole names (kanji or romaji)
+/// against the start of the (full-width-normalized) input, in table order
+/// (newest first); every kanji name in the table is two characters long, so
+/// at most one of them can match. Whole-name matching is needed
+/// because many historical era names share leading characters (several
+/// begin with `天`, for instance), so matching on a single initial would be
+/// ambiguous once the table goes beyond the five modern eras.
+///
+/// As a fallback, the single-letter/kanji-initial shorthand used by
+/// `JisX0301Extended`/`JisX0301ExtendedWithKanji` (`M`/`明`, `T`/`大`,
+/// `S`/`昭`, `H`/`平`, `R`/`令`) is still recognized, since those initials
+/// were chosen specifically not to collide among the five modern eras. If
+/// no meta attribute is present at all, the Gengo is assumed to be the
+/// current one (Reiwa).
 ///
 /// ## Example
 /// ```rust
 /// use wareki_conv::conv::gengo_resolve;
 /// use wareki_conv::conv::Gengo;
 ///
-/// assert_eq!(gengo_resolve("R01.02.03"), Some(Gengo::Reiwa))
+/// assert_eq!(gengo_resolve("R01.02.03"), Some(Gengo::reiwa()))
 /// ```
 pub fn gengo_resolve(wareki: &str) -> Option<Gengo> {
-    let meiji = vec!['M', '明'];
-    let taisho = vec!['T', '大'];
-    let showa = vec!['S', '昭'];
-    let heisei = vec!['H', '平'];
-    #[allow(unused_variables)]
-    // Currently, date with no meta attribute is mapped to this value.
-    let reiwa = vec!['R', '令'];
-
     let wareki_half = to_half_width(wareki);
-    let first_char = wareki_half.chars().nth(0);
-    let gengo = match first_char {
-        Some(x) if meiji.contains(&x) => Some(Gengo::Meiji),
-        Some(x) if taisho.contains(&x) => Some(Gengo::Taisho),
-        Some(x) if showa.contains(&x) => Some(Gengo::Showa),
-        Some(x) if heisei.contains(&x) => Some(Gengo::Heisei),
+
+    if let Some(gengo) = gengo_table()
+        .into_iter()
+        .find(|gengo| wareki_half.starts_with(gengo.kanji) || wareki_half.starts_with(gengo.romaji))
+    {
+        return Some(gengo);
+    }
+
+    let first_char = wareki_half.chars().next();
+    match first_char {
+        Some('M') | Some('明') => Some(Gengo::meiji()),
+        Some('T') | Some('大') => Some(Gengo::taisho()),
+        Some('S') | Some('昭') => Some(Gengo::showa()),
+        Some('H') | Some('平') => Some(Gengo::heisei()),
         // If no meta attribute is appended, the Gengo is assumed to be the current one.
-        _ => Some(Gengo::Reiwa),
-    };
+        _ => Some(Gengo::reiwa()),
+    }
+}
 
-    gengo
+/// Parses the year/month/day out of a `<name><year>年<month>月<day>日`
+/// string, where `name_len` is the character length of `<name>` (e.g. 2 for
+/// a Gengo kanji name or for `皇紀`).
+pub(crate) fn extract_kanji_ymd(wareki_half: &str, name_len: usize) -> Result<Vec<u32>, WarekiError> {
+    let tmp: String = wareki_half
+        .chars()
+        .skip(name_len)
+        .filter(|x| x != &'日')
+        .map(|x| if x.is_ascii_digit() { x } else { '.' })
+        .collect();
+    let ymd_elements: Vec<u32> = tmp
+        .split('.')
+        .map(|x| x.parse().map_err(|_| WarekiError::InvalidNumber))
+        .collect::<Result<_, _>>()?;
+    if ymd_elements.len() != 3 {
+        return Err(WarekiError::UnrecognizedFormat);
+    }
+    Ok(ymd_elements)
 }
 
 /// Converts Wareki (JIS X 0301) based date into ISO based one
@@ -249,39 +661,28 @@ pub fn gengo_resolve(wareki: &str) -> Option<Gengo> {
 /// era. For example, the first day of the Heisei is January 8. This
 /// library does not take such conditions into account and assumes that the
 /// input values are correct.
-pub fn convert(wareki: &str) -> Result<Option<DateTime<Utc>>, regex::Error> {
+pub fn convert(wareki: &str) -> Result<Option<DateTime<Utc>>, WarekiError> {
     let mut wareki_half = to_half_width(wareki);
     // Replace `"元年"` to `"1年"`
     wareki_half = wareki_half.replace("元", "1");
-    let date_type = match find_type(&wareki_half) {
-        Ok(x) => x,
-        Err(e) => return Err(e),
-    };
+    let date_type = find_type(&wareki_half)?;
     let gengo = gengo_resolve(&wareki_half);
     let ymd_elements: Vec<u32>;
 
     match date_type {
-        Some(DateType::SeparatedWithKanji) => {
-            let tmp: String = wareki_half
-                .chars()
-                .skip(2)
-                .filter(|x| x != &'日')
-                .map(|x| if x.is_ascii_digit() { x } else { '.' })
-                .collect();
-            ymd_elements = tmp
-                .split('.')
-                .into_iter()
-                .map(|x| x.parse().unwrap())
-                .collect();
-            assert_eq!(ymd_elements.len(), 3);
+        Some(DateType::SeparatedWithKanji) | Some(DateType::Koki) => {
+            // Both notations spell out `<name><year>年<month>月<day>日`, and
+            // every era/"皇紀" name in use here is exactly 2 characters.
+            ymd_elements = extract_kanji_ymd(&wareki_half, 2)?;
         }
         Some(DateType::JisX0301Basic) => {
             ymd_elements = wareki_half
                 .split('.')
-                .into_iter()
-                .map(|x| x.parse().unwrap())
-                .collect();
-            assert_eq!(ymd_elements.len(), 3);
+                .map(|x| x.parse().map_err(|_| WarekiError::InvalidNumber))
+                .collect::<Result<_, _>>()?;
+            if ymd_elements.len() != 3 {
+                return Err(WarekiError::UnrecognizedFormat);
+            }
         }
         Some(DateType::JisX0301Extended) | Some(DateType::JisX0301ExtendedWithKanji) => {
             ymd_elements = wareki_half
@@ -289,32 +690,23 @@ pub fn convert(wareki: &str) -> Result<Option<DateTime<Utc>>, regex::Error> {
                 .skip(1)
                 .collect::<String>()
                 .split('.')
-                .into_iter()
-                .map(|x| x.parse().unwrap())
-                .collect();
-            assert_eq!(ymd_elements.len(), 3);
+                .map(|x| x.parse().map_err(|_| WarekiError::InvalidNumber))
+                .collect::<Result<_, _>>()?;
+            if ymd_elements.len() != 3 {
+                return Err(WarekiError::UnrecognizedFormat);
+            }
         }
         None => return Ok(None),
     }
 
-    // Converts year corresponding to Gengo
-    let year = match gengo {
-        Some(Gengo::Meiji) => {
-            ymd_elements.get(0).unwrap().clone() as i32 + Gengo::first_year(&Gengo::Meiji) - 1
-        }
-        Some(Gengo::Taisho) => {
-            ymd_elements.get(0).unwrap().clone() as i32 + Gengo::first_year(&Gengo::Taisho) - 1
-        }
-        Some(Gengo::Showa) => {
-            ymd_elements.get(0).unwrap().clone() as i32 + Gengo::first_year(&Gengo::Showa) - 1
-        }
-        Some(Gengo::Heisei) => {
-            ymd_elements.get(0).unwrap().clone() as i32 + Gengo::first_year(&Gengo::Heisei) - 1
-        }
-        Some(Gengo::Reiwa) => {
-            ymd_elements.get(0).unwrap().clone() as i32 + Gengo::first_year(&Gengo::Reiwa) - 1
+    let year = if date_type == Some(DateType::Koki) {
+        *ymd_elements.get(0).unwrap() as i32 - KOKI_EPOCH_OFFSET
+    } else {
+        // Converts year corresponding to Gengo
+        match gengo {
+            Some(gengo) => *ymd_elements.get(0).unwrap() as i32 + gengo.first_year() - 1,
+            None => return Err(WarekiError::UnknownEra),
         }
-        None => return Ok(None),
     };
 
     let date = Date::new(
@@ -325,7 +717,58 @@ pub fn convert(wareki: &str) -> Result<Option<DateTime<Utc>>, regex::Error> {
 
     let date_time: DateTime<Utc> = Utc
         .with_ymd_and_hms(date.year(), date.month(), date.day(), 00, 00, 00)
-        .unwrap();
+        .single()
+        .ok_or(WarekiError::InvalidDate)?;
+
+    Ok(Some(date_time))
+}
+
+/// Converts a Wareki date like [`convert`], but additionally rejects dates
+/// that fall outside the real boundaries of the matched era.
+///
+/// `convert` assumes its input is correct and offsets purely by year, so it
+/// happily accepts `平成1年1月3日` even though Heisei did not begin until
+/// 1989-01-08. `convert_strict` uses [`Gengo::start_date`] and
+/// [`Gengo::end_date`] to catch exactly that kind of transcription error,
+/// which matters when converting dates off of government documents.
+///
+/// ## Example
+/// ```rust
+/// use wareki_conv::conv::{convert_strict, WarekiError};
+///
+/// assert!(convert_strict("平成1年1月8日").is_ok());
+/// assert!(matches!(
+///     convert_strict("平成1年1月7日"),
+///     Err(WarekiError::OutOfEraRange)
+/// ));
+/// ```
+pub fn convert_strict(wareki: &str) -> Result<Option<DateTime<Utc>>, WarekiError> {
+    let mut wareki_half = to_half_width(wareki);
+    wareki_half = wareki_half.replace('元', "1");
+
+    let date_time = match convert(&wareki_half)? {
+        Some(date_time) => date_time,
+        None => return Ok(None),
+    };
+
+    // Kōki dates aren't counted against a Gengo, so there's no era boundary
+    // to validate; `gengo_resolve` has no notion of `DateType::Koki` and
+    // would otherwise fall back to validating against the current era.
+    if find_type(&wareki_half)? == Some(DateType::Koki) {
+        return Ok(Some(date_time));
+    }
+
+    let gengo = gengo_resolve(&wareki_half).ok_or(WarekiError::UnknownEra)?;
+    let date = date_time.date_naive();
+
+    if date < gengo.start_date() {
+        return Err(WarekiError::OutOfEraRange);
+    }
+    if let Some(end_date) = gengo.end_date() {
+        if date > end_date {
+            return Err(WarekiError::OutOfEraRange);
+        }
+    }
 
     Ok(Some(date_time))
 }
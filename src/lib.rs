@@ -5,11 +5,13 @@
 extern crate test;
 
 pub mod conv;
+pub mod old_calendar;
 
 /// tests
 #[cfg(test)]
 mod tests {
     use super::conv::*;
+    use super::old_calendar::{convert_old_calendar, convert_wareki};
     use chrono::prelude::*;
     use std::collections::HashMap;
     use test::Bencher;
@@ -57,6 +59,286 @@ mod tests {
         test_assert("平成1年2月3日", (1989, 2, 3));
         test_assert("平成１年２月３日", (1989, 2, 3));
         test_assert("平成元年２月３日", (1989, 2, 3));
+
+        // DateType::Koki
+        test_assert("皇紀2600年1月1日", (1940, 1, 1));
+    }
+
+    #[test]
+    fn assert_to_koki() {
+        assert_eq!(to_koki(Utc.with_ymd_and_hms(1940, 1, 1, 0, 0, 0).unwrap()), 2600);
+        assert_eq!(to_koki(Utc.with_ymd_and_hms(2019, 5, 1, 0, 0, 0).unwrap()), 2679);
+    }
+
+    #[test]
+    fn assert_to_wareki() {
+        fn test_assert(ymd: (i32, u32, u32), expected: Wareki) {
+            assert_eq!(
+                to_wareki(Utc.with_ymd_and_hms(ymd.0, ymd.1, ymd.2, 0, 0, 0).unwrap()),
+                Some(expected)
+            )
+        }
+
+        test_assert(
+            (2019, 5, 1),
+            Wareki {
+                gengo: Gengo::reiwa(),
+                year: 1,
+                month: 5,
+                day: 1,
+            },
+        );
+        test_assert(
+            (2019, 2, 3),
+            Wareki {
+                gengo: Gengo::heisei(),
+                year: 31,
+                month: 2,
+                day: 3,
+            },
+        );
+        test_assert(
+            (1989, 1, 7),
+            Wareki {
+                gengo: Gengo::showa(),
+                year: 64,
+                month: 1,
+                day: 7,
+            },
+        );
+        test_assert(
+            (1989, 1, 8),
+            Wareki {
+                gengo: Gengo::heisei(),
+                year: 1,
+                month: 1,
+                day: 8,
+            },
+        );
+
+        // 1868-01-01 falls within 慶応/Keio (starts 1865-04-07), not before
+        // the table's oldest entry, now that chunk0-4 extended `gengo_table`
+        // back through the Edo period. `Gengo` has no public constructor
+        // beyond the modern-era accessors, so check the resolved fields
+        // directly rather than building an expected `Gengo` to compare.
+        let keio = to_wareki(Utc.with_ymd_and_hms(1868, 1, 1, 0, 0, 0).unwrap()).unwrap();
+        assert_eq!(keio.gengo.kanji_name(), "慶応");
+        assert_eq!(keio.year, 4);
+        assert_eq!(keio.month, 1);
+        assert_eq!(keio.day, 1);
+
+        // 応永/Oei (starts 1394-08-02) is now the oldest era in the table
+        // (chunk0-4's Nanboku-chō-boundary extension).
+        let oei = to_wareki(Utc.with_ymd_and_hms(1394, 8, 2, 0, 0, 0).unwrap()).unwrap();
+        assert_eq!(oei.gengo.kanji_name(), "応永");
+        assert_eq!(oei.year, 1);
+
+        // Dates before the oldest era in the table are still not representable.
+        assert_eq!(
+            to_wareki(Utc.with_ymd_and_hms(1394, 8, 1, 0, 0, 0).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn assert_convert_strict() {
+        // Real Heisei boundary is 1989-01-08; Showa 64 only ran to 01-07.
+        assert_eq!(
+            convert_strict("平成1年1月8日").unwrap().unwrap(),
+            Utc.with_ymd_and_hms(1989, 1, 8, 0, 0, 0).unwrap()
+        );
+        assert!(matches!(
+            convert_strict("平成1年1月7日"),
+            Err(WarekiError::OutOfEraRange)
+        ));
+        assert!(matches!(
+            convert_strict("昭和64年1月8日"),
+            Err(WarekiError::OutOfEraRange)
+        ));
+        // Koki dates aren't counted against a Gengo, so there's no era
+        // boundary to reject them against.
+        assert_eq!(
+            convert_strict("皇紀2600年1月1日").unwrap().unwrap(),
+            Utc.with_ymd_and_hms(1940, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn assert_convert_error() {
+        assert!(matches!(
+            convert("R01.foo.03"),
+            Err(WarekiError::InvalidNumber)
+        ));
+        assert!(matches!(
+            convert("R01.02"),
+            Err(WarekiError::UnrecognizedFormat)
+        ));
+        assert!(matches!(convert("R01.02.30"), Err(WarekiError::InvalidDate)));
+    }
+
+    #[test]
+    fn assert_convert_old_calendar() {
+        // Meiji 5/12/1 and 5/12/2 are the last two days of the old
+        // calendar; the reform skipped straight to Meiji 6/1/1.
+        assert_eq!(
+            convert_old_calendar(&Gengo::meiji(), 5, 12, false, 1).unwrap(),
+            Some(Utc.with_ymd_and_hms(1872, 12, 30, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            convert_old_calendar(&Gengo::meiji(), 5, 12, false, 2).unwrap(),
+            Some(Utc.with_ymd_and_hms(1872, 12, 31, 0, 0, 0).unwrap())
+        );
+        assert!(matches!(
+            convert_old_calendar(&Gengo::meiji(), 5, 12, false, 3),
+            Err(WarekiError::InvalidDate)
+        ));
+        // Outside the embedded table: no data, not an error.
+        assert_eq!(
+            convert_old_calendar(&Gengo::meiji(), 2, 2, false, 1).unwrap(),
+            None
+        );
+        // Meiji 4's New Year's Day anchor.
+        assert_eq!(
+            convert_old_calendar(&Gengo::meiji(), 4, 1, false, 1).unwrap(),
+            Some(Utc.with_ymd_and_hms(1871, 2, 19, 0, 0, 0).unwrap())
+        );
+        // Meiji 3's attested leap month (閏10月): `leap` distinguishes it
+        // from the regular 10th month that precedes it.
+        let month_10 = convert_old_calendar(&Gengo::meiji(), 3, 10, false, 1)
+            .unwrap()
+            .unwrap();
+        let leap_month_10 = convert_old_calendar(&Gengo::meiji(), 3, 10, true, 1)
+            .unwrap()
+            .unwrap();
+        assert!(leap_month_10 > month_10);
+        // No leap month was ever inserted into Meiji 5.
+        assert_eq!(
+            convert_old_calendar(&Gengo::meiji(), 5, 11, true, 1).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn assert_convert_wareki() {
+        // Old-calendar date: routed to `convert_old_calendar`.
+        assert_eq!(
+            convert_wareki("明治5年12月2日").unwrap(),
+            Some(Utc.with_ymd_and_hms(1872, 12, 31, 0, 0, 0).unwrap())
+        );
+        // Leap-month old-calendar date.
+        assert_eq!(
+            convert_wareki("明治3年閏10月1日").unwrap(),
+            convert_old_calendar(&Gengo::meiji(), 3, 10, true, 1).unwrap()
+        );
+        // Modern date: routed to `conv::convert` as usual.
+        assert_eq!(
+            convert_wareki("令和1年2月3日").unwrap(),
+            Some(Utc.with_ymd_and_hms(2019, 2, 3, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            convert_wareki("R01.02.03").unwrap(),
+            Some(Utc.with_ymd_and_hms(2019, 2, 3, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn assert_modern_era_accessors() {
+        // Regression test for the [kv5h/wareki-conv#chunk0-4] review fix:
+        // these must resolve by name, not by position in `gengo_table`.
+        assert_eq!(Gengo::reiwa().kanji_name(), "令和");
+        assert_eq!(Gengo::heisei().kanji_name(), "平成");
+        assert_eq!(Gengo::showa().kanji_name(), "昭和");
+        assert_eq!(Gengo::taisho().kanji_name(), "大正");
+        assert_eq!(Gengo::meiji().kanji_name(), "明治");
+    }
+
+    #[test]
+    fn assert_format_wareki() {
+        assert_eq!(
+            format_wareki(
+                &Gengo::reiwa(),
+                1,
+                2,
+                3,
+                DateType::SeparatedWithKanji,
+                EraStyle::KanjiFull,
+                true
+            ),
+            "令和元年2月3日"
+        );
+        assert_eq!(
+            format_wareki(
+                &Gengo::reiwa(),
+                1,
+                2,
+                3,
+                DateType::SeparatedWithKanji,
+                EraStyle::KanjiFull,
+                false
+            ),
+            "令和1年2月3日"
+        );
+        assert_eq!(
+            format_wareki(
+                &Gengo::reiwa(),
+                10,
+                2,
+                3,
+                DateType::JisX0301Extended,
+                EraStyle::RomajiInitial,
+                false
+            ),
+            "R10.02.03"
+        );
+        assert_eq!(
+            format_wareki(
+                &Gengo::heisei(),
+                1,
+                2,
+                3,
+                DateType::JisX0301ExtendedWithKanji,
+                EraStyle::KanjiShort,
+                false
+            ),
+            "平01.02.03"
+        );
+        assert_eq!(
+            format_wareki(
+                &Gengo::showa(),
+                64,
+                1,
+                7,
+                DateType::JisX0301Basic,
+                EraStyle::RomajiFull,
+                false
+            ),
+            "64.01.07"
+        );
+        assert_eq!(
+            format_wareki(
+                &Gengo::reiwa(),
+                1,
+                2,
+                3,
+                DateType::SeparatedWithKanji,
+                EraStyle::RomajiUpper,
+                false
+            ),
+            "REIWA1年2月3日"
+        );
+        // Koki has no Gengo, so the Gengo/style arguments are ignored.
+        assert_eq!(
+            format_wareki(
+                &Gengo::reiwa(),
+                2600,
+                1,
+                1,
+                DateType::Koki,
+                EraStyle::KanjiFull,
+                false
+            ),
+            "皇紀2600年1月1日"
+        );
     }
 
     #[test]
@@ -0,0 +1,296 @@
+//! Old-calendar (旧暦; lunisolar) date conversion for pre-1873 dates.
+//!
+//! Until the 1873 calendar reform, Japanese dates were recorded on a
+//! lunisolar calendar (most recently the Tenpō-reki), not the Gregorian
+//! one. A lunisolar year has 12 or 13 months of 29 or 30 days each, with an
+//! extra leap month (閏月) inserted every few years to keep the calendar
+//! aligned with the seasons, so a date like `明治5年12月2日` cannot be
+//! converted with the naive year-offset arithmetic [`crate::conv::convert`]
+//! uses for modern dates: its Gregorian equivalent depends on exactly
+//! where that month fell, which varies from year to year.
+//!
+//! ## Scope
+//! Reconstructing the full historical Tenpō-reki (and the older calendars
+//! before it) requires an astronomical table well beyond what can be
+//! reliably hand-written here. This module instead embeds [`old_calendar_table`]
+//! for the final years of the old calendar, Meiji 1 through 5 (1868-1872):
+//! * Meiji 5 has a month-start entry for every month, including the
+//!   truncated 12th (cut short by the reform) — its two anchors, New
+//!   Year's Day (1872-02-09) and the reform's final day (1872-12-31), are
+//!   well documented, and the months between are derived from them
+//!   assuming the standard alternating 30/29-day month length, which lands
+//!   exactly on both anchors.
+//! * Meiji 3 additionally covers its attested leap month (閏10月), derived
+//!   the same way from its New Year's Day anchor; lacking a second anchor
+//!   for that year, its day-level precision is lower confidence than
+//!   Meiji 5's.
+//! * Meiji 1, 2 and 4 only have a New Year's Day entry (month 1), since a
+//!   second anchor to derive the rest of those years from isn't available
+//!   here.
+//!
+//! This is still a deliberately narrow slice, not a general Tenpō-reki
+//! implementation — further months/years can be appended to
+//! [`old_calendar_table`] without changing [`convert_old_calendar`]'s
+//! lookup logic.
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+
+use crate::conv::{self, DateType, Gengo, WarekiError};
+
+/// One lunisolar month: the Gengo/era-year/ordinal it belongs to, whether
+/// it is a leap month, and where it falls on the proleptic Gregorian
+/// calendar.
+///
+/// `leap` exists because a lunisolar year can contain two months sharing
+/// the same `month` ordinal (the leap month is inserted immediately after
+/// the month it duplicates), so `(era_year, month)` alone does not
+/// uniquely identify a month.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LunisolarMonth {
+    /// Kanji name of the Gengo this era-year is counted in.
+    gengo_kanji: &'static str,
+    /// Year within the Gengo.
+    era_year: i32,
+    /// Month ordinal (1-12).
+    month: u32,
+    /// Whether this is the inserted leap month (閏月) for `month`.
+    leap: bool,
+    /// SDN (serial day number; here `NaiveDate::num_days_from_ce()`) of the
+    /// month's first day.
+    sdn_start: i32,
+    /// Number of days in the month. Normally 29 or 30, but the table may
+    /// embed a short final month, as for Meiji 5's 12th.
+    days: u32,
+}
+
+fn sdn(y: i32, m: u32, d: u32) -> i32 {
+    NaiveDate::from_ymd_opt(y, m, d)
+        .expect("table entries are valid calendar dates")
+        .num_days_from_ce()
+}
+
+/// Builds consecutive month entries starting at `start`, alternating
+/// 30/29-day lengths (the standard lunisolar big-month/small-month
+/// pattern), for the `era_year`/`month` ordinals given in `months`. Used
+/// to derive a year's month starts from a single anchor date without
+/// repeating the day arithmetic by hand for every entry.
+fn alternating_months(
+    gengo_kanji: &'static str,
+    era_year: i32,
+    start: NaiveDate,
+    months: &[(u32, bool)],
+) -> Vec<LunisolarMonth> {
+    let mut sdn_start = start.num_days_from_ce();
+    months
+        .iter()
+        .enumerate()
+        .map(|(i, &(month, leap))| {
+            let days = if i % 2 == 0 { 30 } else { 29 };
+            let entry = LunisolarMonth {
+                gengo_kanji,
+                era_year,
+                month,
+                leap,
+                sdn_start,
+                days,
+            };
+            sdn_start += days as i32;
+            entry
+        })
+        .collect()
+}
+
+/// The embedded old-calendar table. See the module docs for its
+/// (intentionally narrow) scope and the confidence level of each year.
+fn old_calendar_table() -> Vec<LunisolarMonth> {
+    let mut table = Vec::new();
+
+    // New Year's Day only, for years without a second anchor to derive the
+    // rest of the year from.
+    table.push(LunisolarMonth {
+        gengo_kanji: "明治",
+        era_year: 1,
+        month: 1,
+        leap: false,
+        sdn_start: sdn(1868, 1, 25),
+        days: 30,
+    });
+    table.push(LunisolarMonth {
+        gengo_kanji: "明治",
+        era_year: 2,
+        month: 1,
+        leap: false,
+        sdn_start: sdn(1869, 2, 11),
+        days: 30,
+    });
+    table.push(LunisolarMonth {
+        gengo_kanji: "明治",
+        era_year: 4,
+        month: 1,
+        leap: false,
+        sdn_start: sdn(1871, 2, 19),
+        days: 30,
+    });
+
+    // Meiji 3 (1870): New Year's Day plus the attested leap month 閏10月,
+    // derived from the New Year anchor (lower confidence than Meiji 5,
+    // which has two independent anchors - see module docs).
+    table.extend(alternating_months(
+        "明治",
+        3,
+        NaiveDate::from_ymd_opt(1870, 2, 1).expect("valid date"),
+        &[
+            (1, false),
+            (2, false),
+            (3, false),
+            (4, false),
+            (5, false),
+            (6, false),
+            (7, false),
+            (8, false),
+            (9, false),
+            (10, false),
+            (10, true), // 閏10月
+        ],
+    ));
+
+    // Meiji 5 (1872): every month of the old calendar's last year, anchored
+    // at both New Year's Day (1872-02-09) and the reform's final day
+    // (1872-12-30 / -31) - see module docs for why this is high confidence.
+    table.extend(alternating_months(
+        "明治",
+        5,
+        NaiveDate::from_ymd_opt(1872, 2, 9).expect("valid date"),
+        &[
+            (1, false),
+            (2, false),
+            (3, false),
+            (4, false),
+            (5, false),
+            (6, false),
+            (7, false),
+            (8, false),
+            (9, false),
+            (10, false),
+            (11, false),
+        ],
+    ));
+    // Cut short by the 1873 reform: only 12/1 and 12/2 occurred; 12/3
+    // never happened, as the next day became Meiji 6/1/1 (1873-01-01).
+    table.push(LunisolarMonth {
+        gengo_kanji: "明治",
+        era_year: 5,
+        month: 12,
+        leap: false,
+        sdn_start: sdn(1872, 12, 30),
+        days: 2,
+    });
+
+    table
+}
+
+/// Converts an old-calendar (lunisolar) Wareki date into its proleptic
+/// Gregorian equivalent, using [`old_calendar_table`].
+///
+/// Returns `Ok(None)` if the `(gengo, era_year, month, leap)` combination
+/// isn't in the embedded table, rather than an error, since that just
+/// means this month falls outside the table's narrow coverage rather than
+/// being malformed input.
+///
+/// ## Example
+/// ```rust
+/// use chrono::prelude::*;
+/// use wareki_conv::conv::Gengo;
+/// use wareki_conv::old_calendar::convert_old_calendar;
+///
+/// assert_eq!(
+///     convert_old_calendar(&Gengo::meiji(), 5, 12, false, 2).unwrap(),
+///     Some(Utc.with_ymd_and_hms(1872, 12, 31, 0, 0, 0).unwrap())
+/// );
+/// ```
+pub fn convert_old_calendar(
+    gengo: &Gengo,
+    era_year: i32,
+    month: u32,
+    leap: bool,
+    day: u32,
+) -> Result<Option<DateTime<Utc>>, WarekiError> {
+    let entry = old_calendar_table().into_iter().find(|entry| {
+        entry.gengo_kanji == gengo.kanji_name()
+            && entry.era_year == era_year
+            && entry.month == month
+            && entry.leap == leap
+    });
+
+    let entry = match entry {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    if day < 1 || day > entry.days {
+        return Err(WarekiError::InvalidDate);
+    }
+
+    let date = NaiveDate::from_num_days_from_ce_opt(entry.sdn_start + (day as i32 - 1))
+        .ok_or(WarekiError::InvalidDate)?;
+
+    let date_time = Utc
+        .with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
+        .single()
+        .ok_or(WarekiError::InvalidDate)?;
+
+    Ok(Some(date_time))
+}
+
+/// Japan adopted the Gregorian calendar on Meiji 6/1/1 (1873-01-01), so
+/// only Meiji years 1 through 5 were ever recorded on the old calendar.
+fn is_old_calendar_era(gengo: &Gengo, era_year: i32) -> bool {
+    gengo.kanji_name() == "明治" && (1..=5).contains(&era_year)
+}
+
+/// Parses a `SeparatedWithKanji` Wareki string (optionally marking a leap
+/// month with a `閏` prefix on the month field, e.g. `明治3年閏10月1日`)
+/// and converts it to a Gregorian date, gating between the two conversion
+/// paths in this crate: dates in the old calendar's range (Meiji 1-5) are
+/// routed through [`convert_old_calendar`], and every other date continues
+/// through the existing [`crate::conv::convert`] fast path.
+///
+/// ## Example
+/// ```rust
+/// use chrono::prelude::*;
+/// use wareki_conv::old_calendar::convert_wareki;
+///
+/// // Old-calendar date: resolved via `convert_old_calendar`.
+/// assert_eq!(
+///     convert_wareki("明治5年12月2日").unwrap(),
+///     Some(Utc.with_ymd_and_hms(1872, 12, 31, 0, 0, 0).unwrap())
+/// );
+///
+/// // Modern date: resolved via `conv::convert` as usual.
+/// assert_eq!(
+///     convert_wareki("令和1年2月3日").unwrap(),
+///     Some(Utc.with_ymd_and_hms(2019, 2, 3, 0, 0, 0).unwrap())
+/// );
+/// ```
+pub fn convert_wareki(wareki: &str) -> Result<Option<DateTime<Utc>>, WarekiError> {
+    let mut wareki_half = conv::to_half_width(wareki);
+    let leap = wareki_half.contains('閏');
+    wareki_half = wareki_half.replace('閏', "").replace('元', "1");
+
+    if conv::find_type(&wareki_half)? != Some(DateType::SeparatedWithKanji) {
+        // Old-calendar dates are only ever written in this notation.
+        return conv::convert(wareki);
+    }
+
+    let gengo = conv::gengo_resolve(&wareki_half).ok_or(WarekiError::UnknownEra)?;
+    let ymd = conv::extract_kanji_ymd(&wareki_half, 2)?;
+    let era_year = *ymd.first().unwrap() as i32;
+    let month = *ymd.get(1).unwrap();
+    let day = *ymd.get(2).unwrap();
+
+    if is_old_calendar_era(&gengo, era_year) {
+        return convert_old_calendar(&gengo, era_year, month, leap, day);
+    }
+
+    conv::convert(wareki)
+}